@@ -0,0 +1,26 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use thai_wbk::segments::trie::{
+    load_dictionary_from_file, segment_thai_text, segment_thai_text_compact, CompactTrie,
+};
+
+const SAMPLE_TEXT: &str = "สวัสดีครับคุณไปที่ไหนสวัสดีครับคุณไปที่ไหน";
+
+fn bench_hashmap_trie(c: &mut Criterion) {
+    let trie = load_dictionary_from_file("datas/lexitron.txt").expect("load lexicon");
+
+    c.bench_function("segment_thai_text (HashMap Trie)", |b| {
+        b.iter(|| segment_thai_text(SAMPLE_TEXT, &trie));
+    });
+}
+
+fn bench_compact_trie(c: &mut Criterion) {
+    let trie = load_dictionary_from_file("datas/lexitron.txt").expect("load lexicon");
+    let compact = CompactTrie::from_trie(&trie);
+
+    c.bench_function("segment_thai_text_compact (CompactTrie)", |b| {
+        b.iter(|| segment_thai_text_compact(SAMPLE_TEXT, &compact));
+    });
+}
+
+criterion_group!(benches, bench_hashmap_trie, bench_compact_trie);
+criterion_main!(benches);
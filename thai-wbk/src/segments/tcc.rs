@@ -0,0 +1,145 @@
+// Thai Character Cluster (TCC) pre-segmentation.
+//
+// Thai orthography has inseparable clusters: a base consonant (or a leading
+// vowel) followed by combining tone marks / vowels that can never stand on
+// their own. Splitting inside one of these clusters produces garbage tokens,
+// so the segmenter groups the input into clusters first and only ever emits
+// unknown spans on cluster boundaries.
+
+// A base consonant that can start a cluster on its own.
+fn is_base_consonant(ch: char) -> bool {
+    matches!(ch, '\u{0e01}'..='\u{0e2e}')
+}
+
+// A leading vowel (SARA E / AE / O / AI MAIMUAN / AI MAIMALAI) that is
+// written before the consonant it belongs to, so it binds to the *next*
+// consonant rather than standing alone.
+fn is_leading_vowel(ch: char) -> bool {
+    matches!(ch, '\u{0e40}'..='\u{0e44}')
+}
+
+// Above/below vowels and tone marks that attach to a preceding base and can
+// never start or stand as their own cluster.
+fn is_combining_mark(ch: char) -> bool {
+    matches!(ch, '\u{0e31}' | '\u{0e34}'..='\u{0e3a}' | '\u{0e47}'..='\u{0e4e}')
+}
+
+const SARA_AM: char = '\u{0e33}';
+
+// Absorb any combining marks following `i`, then close the cluster with a
+// trailing SARA AM if present, returning the new position.
+fn absorb_marks(chars: &[char], mut i: usize) -> usize {
+    let n = chars.len();
+    while i < n && is_combining_mark(chars[i]) {
+        i += 1;
+    }
+    if i < n && chars[i] == SARA_AM {
+        i += 1;
+    }
+    i
+}
+
+/// Group `text` into Thai Character Clusters, returning `(start, end)` char
+/// index spans that the tokenizer should treat as atomic. Digits, Latin
+/// letters, whitespace, and any other non-Thai character each form their own
+/// single-character cluster.
+pub fn segment_clusters(text: &str) -> Vec<(usize, usize)> {
+    let chars: Vec<char> = text.chars().collect();
+    let n = chars.len();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < n {
+        let start = i;
+        let ch = chars[i];
+
+        if is_leading_vowel(ch) {
+            i += 1;
+            if i < n && is_base_consonant(chars[i]) {
+                i += 1;
+                i = absorb_marks(&chars, i);
+            }
+        } else if is_base_consonant(ch) {
+            i += 1;
+            i = absorb_marks(&chars, i);
+        } else {
+            // Standalone digit/Latin/whitespace/other: its own cluster.
+            i += 1;
+        }
+
+        spans.push((start, i));
+    }
+
+    spans
+}
+
+/// For every char position in `text`, the end (char index) of the Thai
+/// Character Cluster that contains it. Segmentation entry points use this to
+/// grow an unknown-token span to the nearest cluster boundary instead of
+/// cutting mid-cluster.
+pub fn cluster_end_at(text: &str) -> Vec<usize> {
+    let mut ends = vec![0usize; text.chars().count()];
+    for (start, end) in segment_clusters(text) {
+        for pos in ends.iter_mut().take(end).skip(start) {
+            *pos = end;
+        }
+    }
+    ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consonant_with_tone_mark_stays_one_cluster() {
+        // ค + ่ (mai ek)
+        let spans = segment_clusters("ค่า");
+        let text: Vec<char> = "ค่า".chars().collect();
+        assert_eq!(spans.len(), 2);
+        let (s, e) = spans[0];
+        assert_eq!(text[s..e].iter().collect::<String>(), "ค่");
+    }
+
+    #[test]
+    fn test_leading_vowel_binds_to_next_consonant() {
+        // เ (leading vowel) + ด
+        let spans = segment_clusters("เด็ก");
+        let text: Vec<char> = "เด็ก".chars().collect();
+        let (s, e) = spans[0];
+        assert_eq!(text[s..e].iter().collect::<String>(), "เด็");
+    }
+
+    #[test]
+    fn test_sara_am_closes_cluster() {
+        // น + ำ (SARA AM)
+        let spans = segment_clusters("นำ");
+        assert_eq!(spans, vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_digits_and_latin_are_standalone_clusters() {
+        let spans = segment_clusters("1ab");
+        assert_eq!(spans, vec![(0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn test_whole_sentence_round_trips() {
+        let text = "สวัสดีครับ";
+        let chars: Vec<char> = text.chars().collect();
+        let spans = segment_clusters(text);
+        assert_eq!(spans.first().unwrap().0, 0);
+        assert_eq!(spans.last().unwrap().1, chars.len());
+        // Spans must be contiguous and non-overlapping.
+        for w in spans.windows(2) {
+            assert_eq!(w[0].1, w[1].0);
+        }
+    }
+
+    #[test]
+    fn test_cluster_end_at_maps_every_position_to_its_cluster_end() {
+        // "ค่า": 'ค' + mai ek form one cluster ending at 2, 'า' its own at 3.
+        let ends = cluster_end_at("ค่า");
+        assert_eq!(ends, vec![2, 2, 3]);
+    }
+}
@@ -1,9 +1,15 @@
 use std::collections::HashMap;
 
+use super::tcc;
+
 // Struct representing a Trie node
 struct TrieNode {
     children: HashMap<char, TrieNode>,
     is_end_of_word: bool,
+    // Corpus frequency of the word ending at this node; meaningless unless
+    // `is_end_of_word` is set. Defaults to 0 until `insert_weighted` marks
+    // the node as a word.
+    weight: u32,
 }
 
 impl TrieNode {
@@ -12,6 +18,7 @@ impl TrieNode {
         TrieNode {
             children: HashMap::new(),
             is_end_of_word: false,
+            weight: 0,
         }
     }
 }
@@ -29,8 +36,10 @@ impl Trie {
         }
     }
 
-    // Method to insert a word into the Trie
-    fn insert(&mut self, word: &str) {
+    /// Insert `word` with an explicit corpus-frequency `weight`, used as a
+    /// tie-break in `segment_thai_text_dag` when multiple segmentations tie
+    /// on token/unknown-char cost.
+    pub fn insert_weighted(&mut self, word: &str, weight: u32) {
         let mut node = &mut self.root;
 
         // Traverse through the characters of the word
@@ -40,6 +49,56 @@ impl Trie {
 
         // Mark the end of the word
         node.is_end_of_word = true;
+        node.weight = weight;
+    }
+
+    /// Remove `word` from the dictionary, pruning any nodes left with no
+    /// children and no word of their own. Returns `false` if `word` wasn't
+    /// present.
+    pub fn remove(&mut self, word: &str) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        Self::remove_recursive(&mut self.root, &chars, 0)
+    }
+
+    fn remove_recursive(node: &mut TrieNode, word: &[char], depth: usize) -> bool {
+        if depth == word.len() {
+            if !node.is_end_of_word {
+                return false;
+            }
+            node.is_end_of_word = false;
+            node.weight = 0;
+            return true;
+        }
+
+        let ch = word[depth];
+        let Some(child) = node.children.get_mut(&ch) else {
+            return false;
+        };
+
+        let removed = Self::remove_recursive(child, word, depth + 1);
+        if removed && child.children.is_empty() && !child.is_end_of_word {
+            node.children.remove(&ch);
+        }
+
+        removed
+    }
+
+    // The corpus-frequency weight of `word`, or 0 if it isn't a dictionary word.
+    fn weight_of(&self, word: &str) -> u32 {
+        let mut node = &self.root;
+
+        for ch in word.chars() {
+            match node.children.get(&ch) {
+                Some(next_node) => node = next_node,
+                None => return 0,
+            }
+        }
+
+        if node.is_end_of_word {
+            node.weight
+        } else {
+            0
+        }
     }
 
     // Method to search for the longest matching word in the Trie
@@ -71,14 +130,129 @@ impl Trie {
             Some(all_matches)
         }
     }
+
+    /// Find every dictionary word within `max_distance` Levenshtein edits of
+    /// `query`, paired with the actual edit distance. Walks the whole Trie
+    /// depth-first while maintaining the current DP row for `query`, so a
+    /// subtree is pruned as soon as every cell in its row exceeds
+    /// `max_distance` rather than comparing against every word individually.
+    /// Keep `max_distance` small (1-2) so the pruning stays effective.
+    pub fn search_fuzzy(&self, query: &str, max_distance: u8) -> Vec<(String, u8)> {
+        let query: Vec<char> = query.chars().collect();
+        // Row length must track `query.len()` exactly regardless of how long
+        // the query is; only the distance *values* saturate at `u8::MAX`
+        // (matching the `saturating_add` used throughout this DP).
+        let first_row: Vec<u8> = (0..=query.len())
+            .map(|i| i.min(u8::MAX as usize) as u8)
+            .collect();
+        let mut path = String::new();
+        let mut results = Vec::new();
+
+        Self::search_fuzzy_node(&self.root, &query, &first_row, max_distance, &mut path, &mut results);
+
+        results
+    }
+
+    fn search_fuzzy_node(
+        node: &TrieNode,
+        query: &[char],
+        row: &[u8],
+        max_distance: u8,
+        path: &mut String,
+        results: &mut Vec<(String, u8)>,
+    ) {
+        let m = query.len();
+
+        if node.is_end_of_word && row[m] <= max_distance {
+            results.push((path.clone(), row[m]));
+        }
+
+        for (&ch, child) in &node.children {
+            let mut next_row = vec![0u8; m + 1];
+            next_row[0] = row[0].saturating_add(1);
+
+            for i in 1..=m {
+                let substitution_cost = if query[i - 1] == ch { 0 } else { 1 };
+                next_row[i] = (next_row[i - 1].saturating_add(1))
+                    .min(row[i].saturating_add(1))
+                    .min(row[i - 1].saturating_add(substitution_cost));
+            }
+
+            // Prune: once the best possible distance in this row already
+            // exceeds max_distance, no word under this node can qualify.
+            if next_row.iter().min().copied().unwrap_or(0) <= max_distance {
+                path.push(ch);
+                Self::search_fuzzy_node(child, query, &next_row, max_distance, path, results);
+                path.pop();
+            }
+        }
+    }
+
+    /// Whether `word` is present in the dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        let mut node = &self.root;
+
+        for ch in word.chars() {
+            match node.children.get(&ch) {
+                Some(next_node) => node = next_node,
+                None => return false,
+            }
+        }
+
+        node.is_end_of_word
+    }
+
+    /// Every dictionary word starting with `prefix`, for autocompletion.
+    /// Returns an empty `Vec` when no word has `prefix` as a path in the Trie.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut node = &self.root;
+
+        for ch in prefix.chars() {
+            match node.children.get(&ch) {
+                Some(next_node) => node = next_node,
+                None => return Vec::new(),
+            }
+        }
+
+        let mut words = Vec::new();
+        let mut suffix = String::new();
+        Self::collect_words(node, &mut suffix, &mut words);
+
+        words
+            .into_iter()
+            .map(|suffix| format!("{prefix}{suffix}"))
+            .collect()
+    }
+
+    fn collect_words(node: &TrieNode, suffix: &mut String, words: &mut Vec<String>) {
+        if node.is_end_of_word {
+            words.push(suffix.clone());
+        }
+
+        for (&ch, child) in &node.children {
+            suffix.push(ch);
+            Self::collect_words(child, suffix, words);
+            suffix.pop();
+        }
+    }
 }
+// Lines are either a bare word or `word\tfrequency` (e.g. a LEXiTRON-style
+// frequency-ranked lexicon); a missing or unparseable frequency column
+// falls back to weight 1.
 pub fn load_dictionary_from_file(file_path: &str) -> Result<Trie, std::io::Error> {
     let mut trie = Trie::new();
 
     // Read the file and insert each word into the Trie
     let lines = std::fs::read_to_string(file_path)?;
     for line in lines.lines() {
-        trie.insert(line);
+        let mut columns = line.splitn(2, '\t');
+        let word = columns.next().unwrap_or("");
+        let weight = columns
+            .next()
+            .and_then(|freq| freq.trim().parse::<u32>().ok())
+            .unwrap_or(1);
+
+        trie.insert_weighted(word, weight);
     }
 
     Ok(trie)
@@ -90,6 +264,10 @@ pub fn segment_thai_text(text: &str, trie: &Trie) -> Vec<String> {
     let mut index = 0;
     let chars: Vec<(usize, char)> = text.char_indices().collect(); // Collect char indices
 
+    // Map each char position to the end of the Thai Character Cluster that
+    // contains it, so an unknown span is never emitted mid-cluster.
+    let cluster_end_at = tcc::cluster_end_at(text);
+
     while index < chars.len() {
         let remaining_text: String = chars[index..].iter().map(|&(_, c)| c).collect(); // Convert remaining chars to string
 
@@ -104,20 +282,297 @@ pub fn segment_thai_text(text: &str, trie: &Trie) -> Vec<String> {
             let word_len = matching_word.chars().count();
             index += word_len;
         } else {
-            // If no match is found, treat the current character as a separate token
-            let mut token = chars[index].1.to_string();  // Use current character
-
-            // Check if the next character is a diacritical mark and should be included
-            if index + 1 < chars.len() {
-                let next_char = chars[index + 1].1;
-                if next_char == '\u{e47}' || next_char == '\u{e48}' || next_char == '\u{e49}' || next_char == '\u{e4a}' || next_char == '\u{e4b}' {
-                    token.push(next_char);  // Combine current character with the tonal mark
-                    index += 1;  // Skip the tonal mark character in the next loop
+            // No dictionary match: emit the whole cluster at `index` as one
+            // unknown token instead of splitting it character by character.
+            let end = cluster_end_at[index];
+            let token: String = chars[index..end].iter().map(|&(_, c)| c).collect();
+
+            result.push(token);
+            index = end;
+        }
+    }
+
+    result
+}
+
+/// A segmented token together with its position in the original string, so
+/// callers (highlighting, NER, search snippets) can map it back onto the
+/// source text without re-scanning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    pub char_start: usize,
+    pub char_end: usize,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub in_dict: bool,
+}
+
+// Like `segment_thai_text`, but keeps char/byte offsets and whether each
+// token came from a dictionary match or the unknown-cluster fallback.
+pub fn segment_thai_text_spans(text: &str, trie: &Trie) -> Vec<Token> {
+    let mut result = Vec::new();
+    let mut index = 0;
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let byte_len = text.len();
+
+    let cluster_end_at = tcc::cluster_end_at(text);
+    let byte_at = |char_pos: usize| chars.get(char_pos).map(|&(b, _)| b).unwrap_or(byte_len);
+
+    while index < chars.len() {
+        let remaining_text: String = chars[index..].iter().map(|&(_, c)| c).collect();
+        let byte_start = chars[index].0;
+
+        if let Some(matches) = trie.search_longest_prefix(&remaining_text) {
+            let matching_word = matches[0].clone();
+            let char_end = index + matching_word.chars().count();
+
+            result.push(Token {
+                text: matching_word,
+                char_start: index,
+                char_end,
+                byte_start,
+                byte_end: byte_at(char_end),
+                in_dict: true,
+            });
+            index = char_end;
+        } else {
+            let char_end = cluster_end_at[index];
+            let token: String = chars[index..char_end].iter().map(|&(_, c)| c).collect();
+
+            result.push(Token {
+                text: token,
+                char_start: index,
+                char_end,
+                byte_start,
+                byte_end: byte_at(char_end),
+                in_dict: false,
+            });
+            index = char_end;
+        }
+    }
+
+    result
+}
+
+// Segment Thai text by building a DAG of dictionary-word edges over the
+// character positions and running a shortest-path DP over it, instead of
+// greedily taking the first match at each position. This avoids the
+// "locally correct, globally wrong" fragmentation that plain greedy/longest
+// matching falls into (see
+// `test_dag_prefers_fewer_unknown_chars_over_greedy_local_match`).
+pub fn segment_thai_text_dag(text: &str, trie: &Trie) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let n = chars.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // Weight unknown characters far more than token count so the DP always
+    // minimizes unknown chars first, and only uses token count as a tie-break.
+    const UNKNOWN_WEIGHT: u32 = 1_000_000;
+
+    // For every position, the (length, weight) of every dictionary word
+    // starting there, computed once up front.
+    let dict_matches: Vec<Vec<(usize, u32)>> = (0..n)
+        .map(|i| {
+            let remaining: String = chars[i..].iter().collect();
+            trie.search_longest_prefix(&remaining)
+                .map(|matches| {
+                    matches
+                        .iter()
+                        .map(|m| (m.chars().count(), trie.weight_of(m)))
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
+        .collect();
+
+    // Map each char position to the end of the Thai Character Cluster that
+    // contains it, same as the other segmentation entry points, so an
+    // unknown fallback edge never splits mid-cluster.
+    let cluster_end_at = tcc::cluster_end_at(text);
+
+    // best[pos] = (prev_pos, cost, came_from_dict, total_word_weight)
+    // `total_word_weight` only tie-breaks segmentations with equal cost,
+    // favoring the one that picked higher-frequency dictionary words.
+    let mut best: Vec<Option<(usize, u32, bool, u32)>> = vec![None; n + 1];
+    best[0] = Some((0, 0, false, 0));
+
+    let relax = |best: &mut Vec<Option<(usize, u32, bool, u32)>>,
+                      to: usize,
+                      from: usize,
+                      cost: u32,
+                      from_dict: bool,
+                      weight: u32| {
+        let better = match best[to] {
+            None => true,
+            Some((_, best_cost, _, best_weight)) => {
+                cost < best_cost || (cost == best_cost && weight > best_weight)
+            }
+        };
+        if better {
+            best[to] = Some((from, cost, from_dict, weight));
+        }
+    };
+
+    for i in 0..n {
+        let Some((_, cost_i, _, weight_i)) = best[i] else {
+            continue;
+        };
+
+        // Dictionary edges: one per word starting at `i`.
+        for &(len, word_weight) in &dict_matches[i] {
+            // Skip words that end inside a cluster, so every reached
+            // position is a cluster boundary.
+            if cluster_end_at[i + len - 1] != i + len {
+                continue;
+            }
+            relax(
+                &mut best,
+                i + len,
+                i,
+                cost_i + 1,
+                true,
+                weight_i.saturating_add(word_weight),
+            );
+        }
+
+        // Fallback edge: emit the whole TCC cluster at `i` as a single
+        // unknown token, exactly like
+        // `segment_thai_text`/`segment_thai_text_spans`/`segment_thai_text_compact`.
+        // It is added at every reached position, whether or not a
+        // dictionary word matched there.
+        // One edge per position keeps this loop O(n) overall instead of the
+        // O(n^2) blowup from re-scanning a run's boundary at every position
+        // inside it.
+        let end = cluster_end_at[i];
+        let unknown_len = (end - i) as u32;
+        relax(&mut best, end, i, cost_i + unknown_len * UNKNOWN_WEIGHT + 1, false, weight_i);
+    }
+
+    // Reconstruct the path from the back-pointers.
+    let mut spans = Vec::new();
+    let mut pos = n;
+    while pos > 0 {
+        let (prev, _, _, _) = best[pos].expect("DP reaches n and every position on its path");
+        spans.push((prev, pos));
+        pos = prev;
+    }
+    spans.reverse();
+
+    spans
+        .into_iter()
+        .map(|(start, end)| chars[start..end].iter().collect())
+        .collect()
+}
+
+/// A flattened, contiguous-node Trie backend built once from a pointer-based
+/// `Trie`. Each node's children are stored as a `Vec<(char, node_index)>`
+/// sorted by `char`, so lookups do a binary search over a small contiguous
+/// slice instead of hashing into scattered `HashMap` buckets. This makes
+/// `CompactTrie` cheap to clone and cache-friendly for large lexicons, at the
+/// cost of no longer being mutable in place (rebuild via `from_trie` instead
+/// of `insert`/`remove`).
+#[derive(Clone)]
+pub struct CompactTrie {
+    // `edges[node]` holds that node's children, sorted by char.
+    edges: Vec<Vec<(char, usize)>>,
+    is_end_of_word: Vec<bool>,
+}
+
+impl CompactTrie {
+    /// Build a `CompactTrie` from an existing pointer-based `Trie`.
+    pub fn from_trie(trie: &Trie) -> Self {
+        let mut edges = Vec::new();
+        let mut is_end_of_word = Vec::new();
+        Self::flatten(&trie.root, &mut edges, &mut is_end_of_word);
+        CompactTrie {
+            edges,
+            is_end_of_word,
+        }
+    }
+
+    // Depth-first flatten; returns the node index just written.
+    fn flatten(
+        node: &TrieNode,
+        edges: &mut Vec<Vec<(char, usize)>>,
+        is_end_of_word: &mut Vec<bool>,
+    ) -> usize {
+        let id = edges.len();
+        edges.push(Vec::new());
+        is_end_of_word.push(node.is_end_of_word);
+
+        let mut children: Vec<(char, usize)> = node
+            .children
+            .iter()
+            .map(|(&ch, child)| (ch, Self::flatten(child, edges, is_end_of_word)))
+            .collect();
+        children.sort_unstable_by_key(|&(ch, _)| ch);
+
+        edges[id] = children;
+        id
+    }
+
+    fn child(&self, node: usize, ch: char) -> Option<usize> {
+        self.edges[node]
+            .binary_search_by_key(&ch, |&(c, _)| c)
+            .ok()
+            .map(|idx| self.edges[node][idx].1)
+    }
+
+    // Same all-matches interface as `Trie::search_longest_prefix`, used by
+    // `segment_thai_text_compact`.
+    fn search_longest_prefix(&self, text: &str) -> Option<Vec<String>> {
+        let mut node = 0;
+        let mut all_matches = Vec::new();
+        let mut current_match = String::new();
+
+        for ch in text.chars() {
+            match self.child(node, ch) {
+                Some(next) => {
+                    current_match.push(ch);
+                    if self.is_end_of_word[next] {
+                        all_matches.push(current_match.clone());
+                    }
+                    node = next;
                 }
+                None => break,
             }
+        }
+
+        if all_matches.is_empty() {
+            None
+        } else {
+            Some(all_matches)
+        }
+    }
+}
+
+// Same as `segment_thai_text`, but backed by a `CompactTrie` instead of the
+// HashMap-per-node `Trie`.
+pub fn segment_thai_text_compact(text: &str, trie: &CompactTrie) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut index = 0;
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+
+    let cluster_end_at = tcc::cluster_end_at(text);
+
+    while index < chars.len() {
+        let remaining_text: String = chars[index..].iter().map(|&(_, c)| c).collect();
+
+        if let Some(matches) = trie.search_longest_prefix(&remaining_text) {
+            let matching_word = &matches[0];
+            result.push(matching_word.clone());
+
+            let word_len = matching_word.chars().count();
+            index += word_len;
+        } else {
+            let end = cluster_end_at[index];
+            let token: String = chars[index..end].iter().map(|&(_, c)| c).collect();
 
             result.push(token);
-            index += 1;
+            index = end;
         }
     }
 
@@ -131,12 +586,12 @@ mod tests {
     // Helper function to create a Trie with Thai words
     fn create_trie_with_thai_words() -> Trie {
         let mut trie = Trie::new();
-        trie.insert("สวัสดี");
-        trie.insert("ครับ");
-        trie.insert("คุณ");
-        trie.insert("ไป");
-        trie.insert("ที่ไหน");
-        trie.insert("สวัสดีครับ");
+        trie.insert_weighted("สวัสดี", 1);
+        trie.insert_weighted("ครับ", 1);
+        trie.insert_weighted("คุณ", 1);
+        trie.insert_weighted("ไป", 1);
+        trie.insert_weighted("ที่ไหน", 1);
+        trie.insert_weighted("สวัสดีครับ", 1);
         trie
     }
 
@@ -164,14 +619,24 @@ mod tests {
             "ครับ".to_string(),
             "คุณ".to_string(),
             "ไป".to_string(),
-            "ไ".to_string(),
-            "ห".to_string(),
+            // 'ไห' is one Thai Character Cluster (leading vowel + consonant)
+            // so the unknown fallback emits it as a single token.
+            "ไห".to_string(),
             "น".to_string(),
         ];
         let result = segment_thai_text(text, &trie);
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_segmentation_unknown_span_keeps_tone_mark_attached() {
+        let trie = create_trie_with_thai_words();
+        let text = "คุณค่า"; // 'ค่า' not in dictionary; 'ค' + mai ek must stay one cluster
+        let expected = vec!["คุณ".to_string(), "ค่".to_string(), "า".to_string()];
+        let result = segment_thai_text(text, &trie);
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn test_segmentation_empty_string() {
         let trie = create_trie_with_thai_words();
@@ -215,4 +680,273 @@ mod tests {
         let result = segment_thai_text(text, &trie);
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_dag_basic() {
+        let trie = create_trie_with_thai_words();
+        let text = "สวัสดีครับคุณไปที่ไหน";
+        // The dictionary also holds the compound "สวัสดีครับ"; since both
+        // splits have zero unknown chars, the DAG picks the one with fewer
+        // tokens, so it wins over "สวัสดี" + "ครับ" separately.
+        let expected = vec![
+            "สวัสดีครับ".to_string(),
+            "คุณ".to_string(),
+            "ไป".to_string(),
+            "ที่ไหน".to_string(),
+        ];
+        let result = segment_thai_text_dag(text, &trie);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_dag_unknown_span_matches_tcc_cluster_granularity() {
+        let trie = create_trie_with_thai_words();
+        let text = "สวัสดีครับคุณไปไหน"; // 'ไหน' not in dictionary
+        // 'ไหน' splits into the same two TCC clusters here as it does under
+        // `segment_thai_text` (see `test_segmentation_partial_match`), so
+        // every segmentation entry point agrees on unknown-span granularity.
+        let expected = vec![
+            "สวัสดีครับ".to_string(),
+            "คุณ".to_string(),
+            "ไป".to_string(),
+            "ไห".to_string(),
+            "น".to_string(),
+        ];
+        let result = segment_thai_text_dag(text, &trie);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_dag_empty_string() {
+        let trie = create_trie_with_thai_words();
+        let result = segment_thai_text_dag("", &trie);
+        assert_eq!(result, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_dag_long_unknown_run_is_one_cluster_token_per_char() {
+        // A long run with no dictionary matches at all used to make the
+        // fallback-edge scan quadratic; it should now resolve in one pass
+        // and, since each 'z' is its own TCC cluster, split one token per char.
+        let trie = create_trie_with_thai_words();
+        let text = "z".repeat(2000);
+        let result = segment_thai_text_dag(&text, &trie);
+        assert_eq!(result, vec!["z".to_string(); 2000]);
+    }
+
+    #[test]
+    fn test_dag_prefers_fewer_unknown_chars_over_greedy_local_match() {
+        // A short early word would leave the rest of the sentence
+        // unparseable under greedy matching; the DAG breaker should instead
+        // pick the split that keeps the whole sentence in the dictionary.
+        let mut trie = Trie::new();
+        trie.insert_weighted("ราคา", 1);
+        trie.insert_weighted("คาถา", 1);
+        trie.insert_weighted("ราคาถูก", 1);
+        let text = "ราคาถูก";
+        let result = segment_thai_text_dag(text, &trie);
+        assert_eq!(result, vec!["ราคาถูก".to_string()]);
+    }
+
+    #[test]
+    fn test_fuzzy_finds_exact_match_at_distance_zero() {
+        let trie = create_trie_with_thai_words();
+        let matches = trie.search_fuzzy("ครับ", 1);
+        assert!(matches.contains(&("ครับ".to_string(), 0)));
+    }
+
+    #[test]
+    fn test_fuzzy_finds_one_substitution_away() {
+        let trie = create_trie_with_thai_words();
+        // 'คุณ' with the last char swapped for 'ด'
+        let matches = trie.search_fuzzy("คุด", 1);
+        assert!(matches.contains(&("คุณ".to_string(), 1)));
+    }
+
+    #[test]
+    fn test_fuzzy_respects_max_distance() {
+        let trie = create_trie_with_thai_words();
+        // 'ไป' vs a completely unrelated word is further than distance 1
+        let matches = trie.search_fuzzy("ไป", 1);
+        assert!(!matches.iter().any(|(w, _)| w == "สวัสดี"));
+    }
+
+    #[test]
+    fn test_fuzzy_no_matches_returns_empty() {
+        let trie = create_trie_with_thai_words();
+        let matches = trie.search_fuzzy("xyz", 1);
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_query_longer_than_255_chars_does_not_panic() {
+        let trie = create_trie_with_thai_words();
+        let long_query = "z".repeat(300);
+        let matches = trie.search_fuzzy(&long_query, 1);
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_contains_known_and_unknown_words() {
+        let trie = create_trie_with_thai_words();
+        assert!(trie.contains("สวัสดี"));
+        assert!(!trie.contains("สวัส")); // a prefix, not a full word
+        assert!(!trie.contains("ไม่มี"));
+    }
+
+    #[test]
+    fn test_words_with_prefix_returns_all_matches() {
+        let mut trie = create_trie_with_thai_words();
+        trie.insert_weighted("สวัสดีตอนเช้า", 1);
+        let mut words = trie.words_with_prefix("สวัสดี");
+        words.sort();
+        assert_eq!(
+            words,
+            vec![
+                "สวัสดี".to_string(),
+                "สวัสดีครับ".to_string(),
+                "สวัสดีตอนเช้า".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_words_with_prefix_unknown_prefix_is_empty() {
+        let trie = create_trie_with_thai_words();
+        assert!(trie.words_with_prefix("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_spans_basic_offsets_and_in_dict_flag() {
+        let trie = create_trie_with_thai_words();
+        let text = "ครับ1";
+        let tokens = segment_thai_text_spans(text, &trie);
+
+        assert_eq!(tokens[0].text, "ครับ");
+        assert_eq!(tokens[0].char_start, 0);
+        assert_eq!(tokens[0].char_end, 4);
+        assert_eq!(tokens[0].byte_start, 0);
+        assert_eq!(tokens[0].byte_end, "ครับ".len());
+        assert!(tokens[0].in_dict);
+
+        assert_eq!(tokens[1].text, "1");
+        assert!(!tokens[1].in_dict);
+    }
+
+    #[test]
+    fn test_spans_round_trip_onto_original_text() {
+        let trie = create_trie_with_thai_words();
+        let text = "สวัสดีครับคุณไปที่ไหน";
+        let tokens = segment_thai_text_spans(text, &trie);
+
+        for token in &tokens {
+            assert_eq!(&text[token.byte_start..token.byte_end], token.text);
+        }
+    }
+
+    #[test]
+    fn test_spans_empty_string() {
+        let trie = create_trie_with_thai_words();
+        assert!(segment_thai_text_spans("", &trie).is_empty());
+    }
+
+    #[test]
+    fn test_remove_unsets_word_without_breaking_longer_words() {
+        let mut trie = Trie::new();
+        trie.insert_weighted("คุณ", 1);
+        trie.insert_weighted("คุณภาพ", 1);
+
+        assert!(trie.remove("คุณ"));
+        assert!(!trie.contains("คุณ"));
+        assert!(trie.contains("คุณภาพ"));
+    }
+
+    #[test]
+    fn test_remove_prunes_now_childless_nodes() {
+        let mut trie = Trie::new();
+        trie.insert_weighted("คุณ", 1);
+        trie.insert_weighted("คุณภาพ", 1);
+
+        trie.remove("คุณ");
+        trie.remove("คุณภาพ");
+
+        assert!(trie.words_with_prefix("คุณ").is_empty());
+        assert!(trie.root.children.is_empty());
+    }
+
+    #[test]
+    fn test_remove_missing_word_returns_false() {
+        let mut trie = create_trie_with_thai_words();
+        assert!(!trie.remove("ไม่มี"));
+    }
+
+    #[test]
+    fn test_dag_breaks_cost_tie_with_higher_word_weight() {
+        let mut trie = Trie::new();
+        // Two equal-cost (2-token) segmentations of "กขคง"; the one built
+        // from higher-weight words should win.
+        trie.insert_weighted("กข", 1);
+        trie.insert_weighted("คง", 1);
+        trie.insert_weighted("กขค", 50);
+        trie.insert_weighted("ง", 1);
+
+        let result = segment_thai_text_dag("กขคง", &trie);
+        assert_eq!(result, vec!["กขค".to_string(), "ง".to_string()]);
+    }
+
+    #[test]
+    fn test_load_dictionary_from_file_parses_frequency_column() {
+        let path = std::env::temp_dir().join("thai_wbk_test_lexicon.txt");
+        std::fs::write(&path, "กข\t1\nกขค\t50\nคง\t1\nง\t1\n").unwrap();
+
+        let trie = load_dictionary_from_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(trie.contains("กขค"));
+        let result = segment_thai_text_dag("กขคง", &trie);
+        assert_eq!(result, vec!["กขค".to_string(), "ง".to_string()]);
+    }
+
+    #[test]
+    fn test_load_dictionary_from_file_defaults_weight_to_one() {
+        let path = std::env::temp_dir().join("thai_wbk_test_lexicon_no_weight.txt");
+        std::fs::write(&path, "ครับ\n").unwrap();
+
+        let trie = load_dictionary_from_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(trie.contains("ครับ"));
+        assert_eq!(trie.weight_of("ครับ"), 1);
+    }
+
+    #[test]
+    fn test_compact_trie_matches_hashmap_trie_segmentation() {
+        let trie = create_trie_with_thai_words();
+        let compact = CompactTrie::from_trie(&trie);
+        let text = "สวัสดีครับคุณไปที่ไหน";
+
+        assert_eq!(
+            segment_thai_text_compact(text, &compact),
+            segment_thai_text(text, &trie),
+        );
+    }
+
+    #[test]
+    fn test_compact_trie_search_longest_prefix_all_matches() {
+        let trie = create_trie_with_thai_words();
+        let compact = CompactTrie::from_trie(&trie);
+
+        let matches = compact.search_longest_prefix("สวัสดีครับ");
+        assert_eq!(
+            matches,
+            Some(vec!["สวัสดี".to_string(), "สวัสดีครับ".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_compact_trie_no_match_returns_none() {
+        let trie = create_trie_with_thai_words();
+        let compact = CompactTrie::from_trie(&trie);
+        assert_eq!(compact.search_longest_prefix("xyz"), None);
+    }
 }
\ No newline at end of file